@@ -0,0 +1,70 @@
+// Measure the privacy/overhead trade-off of a defense against a labeled
+// website-fingerprinting dataset in a single command: build a constant-rate
+// (Tamaraw-style) machine from `defenses`, simulate every trace in a
+// directory through it with `dataset::simulate_directory`, and report
+// overhead and k-NN classification accuracy with `eval::evaluate`.
+
+use std::env;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+
+use maybenot::Machine;
+use maybenot_simulator::network::Network;
+
+use maybenot_defenses::dataset;
+use maybenot_defenses::defenses;
+use maybenot_defenses::eval::{self, LabeledTrace};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    assert!(
+        args.len() == 2,
+        "Usage: {} <dataset directory of \"<label>-<instance>\" trace files>",
+        &args[0]
+    );
+    let dir = Path::new(&args[1]);
+
+    // A Tamaraw-style constant-rate defense: one padding cell every 10 ms, in
+    // batches of 10, rounded up to cover a 100-packet transfer.
+    let machine_str = defenses::generate_constant_rate_machine(0.01, 10, 100, u64::MAX, 1.0);
+    let machine = Machine::from_str(&machine_str).unwrap();
+
+    let network = Network::new(Duration::from_millis(10), None);
+    let results = dataset::simulate_directory(dir, &[machine.clone()], &[machine], &network, 10_000, None)
+        .expect("failed to load dataset directory");
+
+    let traces: Vec<LabeledTrace> = results
+        .iter()
+        .map(|result| LabeledTrace {
+            label: label_for_path(&result.path),
+            packets: result.packets.clone(),
+        })
+        .collect();
+
+    let num_classes = traces.iter().map(|t| t.label).max().map_or(0, |max| max + 1);
+
+    // An 80/20 train/test split over the traces, in the (sorted) order
+    // `simulate_directory` returned them.
+    let split = (traces.len() * 4) / 5;
+    let (train, test) = traces.split_at(split);
+
+    let report = eval::evaluate(train, test, num_classes, 5);
+
+    println!("traces: {} train, {} test", train.len(), test.len());
+    println!("top-1 accuracy: {:.3}", report.top1_accuracy);
+    println!("top-5 accuracy: {:.3}", report.topk_accuracy);
+    println!("confusion matrix: {:?}", report.confusion);
+}
+
+// Dataset files are named "<label>-<instance>", e.g. "3-0" for the first
+// instance of website 3; the label is the class the k-NN classifier and
+// confusion matrix are built against.
+fn label_for_path(path: &str) -> usize {
+    Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.split('-').next())
+        .and_then(|label| label.parse().ok())
+        .unwrap_or(0)
+}
@@ -0,0 +1,152 @@
+//! Dataset loader with parallel batch simulation.
+//!
+//! Loads a directory of "time,direction,size" trace files, runs each through
+//! `parse_trace` + `sim` against a shared set of machines, and fans the
+//! batch out across threads with a deterministic per-trace seed (see
+//! `seed_for_path`).
+
+use std::fs;
+use std::path::Path;
+
+use maybenot::{event::TriggerEvent, Machine};
+use maybenot_simulator::{network::Network, parse_trace, sim};
+use rayon::prelude::*;
+
+use crate::eval::EvalPacket;
+use crate::link::LinkModel;
+use crate::sizing::{self, SizeDist};
+
+/// The defended trace and a reproducibility seed for a single simulated
+/// input file.
+pub struct TraceResult {
+    pub path: String,
+    pub seed: u64,
+    pub packets: Vec<EvalPacket>,
+}
+
+/// Load every trace file in `dir`, run each through the simulator against
+/// `client_machines`/`server_machines` over `network`, and return one
+/// [`TraceResult`] per file. Traces are simulated in parallel; each trace's
+/// `seed` is derived deterministically from its file path and used to size
+/// its padding packets (see `sizing::apply_padding_sizes`) and, if
+/// `link_template` is given, to reseed a per-trace clone of it (see
+/// `LinkModel::apply_to_trace`) -- so the parts of the pipeline this crate
+/// controls are reproducible regardless of thread scheduling.
+pub fn simulate_directory(
+    dir: &Path,
+    client_machines: &[Machine],
+    server_machines: &[Machine],
+    network: &Network,
+    max_trace_len: usize,
+    link_template: Option<&LinkModel>,
+) -> std::io::Result<Vec<TraceResult>> {
+    let mut paths: Vec<_> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+    paths.sort();
+
+    paths
+        .par_iter()
+        .map(|path| {
+            simulate_file(
+                path,
+                client_machines,
+                server_machines,
+                network,
+                max_trace_len,
+                link_template,
+            )
+        })
+        .collect()
+}
+
+fn simulate_file(
+    path: &Path,
+    client_machines: &[Machine],
+    server_machines: &[Machine],
+    network: &Network,
+    max_trace_len: usize,
+    link_template: Option<&LinkModel>,
+) -> std::io::Result<TraceResult> {
+    let raw_trace = fs::read_to_string(path)?;
+    let seed = seed_for_path(path);
+    // The simulator has no way to report a synthetic packet's size (see
+    // `crate::sizing`), but it does preserve the real packets' relative
+    // order, so the sizes from the input trace can be replayed onto them in
+    // order as the defended trace is built below.
+    let real_sizes: Vec<u64> = raw_trace
+        .lines()
+        .filter_map(|line| line.split(',').nth(2))
+        .filter_map(|size| size.parse().ok())
+        .collect();
+
+    let mut input_trace = parse_trace(&raw_trace, network);
+    let trace = sim(
+        client_machines,
+        server_machines,
+        &mut input_trace,
+        network.delay,
+        max_trace_len,
+        true,
+    );
+
+    let starting_time = trace.first().map(|p| p.time);
+    let mut next_real_size = real_sizes.into_iter();
+    let mut packets: Vec<EvalPacket> = trace
+        .into_iter()
+        .filter(|p| p.client)
+        .filter_map(|p| {
+            let start = starting_time?;
+            let outgoing = match p.event {
+                TriggerEvent::TunnelSent => true,
+                TriggerEvent::TunnelRecv => false,
+                _ => return None,
+            };
+            let size = if p.contains_padding {
+                0
+            } else {
+                next_real_size.next().unwrap_or(0)
+            };
+            Some(EvalPacket {
+                time: p.time - start,
+                outgoing,
+                is_padding: p.contains_padding,
+                size,
+            })
+        })
+        .collect();
+
+    let size_dist = SizeDist::empirical_from_trace(
+        &packets
+            .iter()
+            .filter(|p| !p.is_padding)
+            .map(|p| p.size)
+            .collect::<Vec<_>>(),
+    );
+    sizing::apply_padding_sizes(&mut packets, &size_dist, seed);
+
+    if let Some(link) = link_template {
+        packets = link.clone().with_seed(seed).apply_to_trace(packets);
+    }
+
+    Ok(TraceResult {
+        path: path.display().to_string(),
+        seed,
+        packets,
+    })
+}
+
+/// Derive a deterministic per-trace seed from its file path, so simulating
+/// the same dataset twice -- regardless of which thread handles which file
+/// -- produces identical results.
+fn seed_for_path(path: &Path) -> u64 {
+    let bytes = path.to_string_lossy();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for b in bytes.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
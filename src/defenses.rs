@@ -0,0 +1,234 @@
+//! Parametric defense library beyond FRONT.
+//!
+//! Reusable `Machine` builders for a handful of well-known website-
+//! fingerprinting defenses, analogous to `generate_machine` in
+//! `maybenot_front`. Each builder is built from the same
+//! `State`/`Trans`/`Action::SendPadding`/`Dist` primitives and exposes the
+//! same `allowed_padding_packets`/`max_padding_frac` budget knobs, so the
+//! resulting machines are directly loadable by the simulator examples and
+//! defenses can be compared head-to-head instead of hand-written base64.
+
+use enum_map::enum_map;
+
+use maybenot::{
+    action::Action,
+    constants::STATE_END,
+    dist::{Dist, DistType},
+    event::Event,
+    state::{State, Trans},
+    Machine,
+};
+
+// A Dist that always samples `value`. `Uniform { low, high }` with
+// `low == high` would do the same, but a zero-width range isn't a value
+// `maybenot`'s Dist sampling is documented to support, so nudge `high` up by
+// an amount too small to matter for any of these builders' timeouts/limits.
+fn fixed_dist(value: f64) -> Dist {
+    let epsilon = (value.abs() + 1.0) * 1e-9;
+    Dist::new(
+        DistType::Uniform {
+            low: value,
+            high: value + epsilon,
+        },
+        0.0,
+        0.0,
+    )
+}
+
+/// Generate a constant-rate, Tamaraw-style machine: pads both directions to a
+/// fixed inter-packet `interval` (seconds), for an `expected_packets`-sized
+/// transfer, rounding the total number of padding packets up to the next
+/// multiple of `grid_l` and stopping there, sent in batches of `grid_l`
+/// cells at a time.
+pub fn generate_constant_rate_machine(
+    interval: f64,
+    grid_l: u32,
+    expected_packets: u32,
+    allowed_padding_packets: u64,
+    max_padding_frac: f64,
+) -> String {
+    assert!(grid_l > 0, "grid_l must be at least 1");
+
+    let rounds = (expected_packets as f64 / grid_l as f64).ceil().max(1.0) as usize;
+
+    let mut states: Vec<State> = Vec::with_capacity(rounds + 1);
+    states.push(generate_start_state());
+
+    for i in 0..rounds {
+        let next_index = if i + 1 == rounds { STATE_END } else { i + 2 };
+        states.push(generate_grid_state(i + 1, next_index, interval, grid_l));
+    }
+
+    let machine = Machine {
+        allowed_padding_packets,
+        max_padding_frac,
+        allowed_blocked_microsec: 0,
+        max_blocking_frac: 0.0,
+        states,
+    };
+
+    machine.serialize()
+}
+
+// A PADDING state that sends one batch of `grid_l` cells at a fixed
+// interval, then hands off to `next_index` -- `STATE_END` for the last
+// batch, so the machine stops once it has padded up to the next multiple of
+// `grid_l`, rather than padding forever.
+fn generate_grid_state(curr_index: usize, next_index: usize, interval: f64, grid_l: u32) -> State {
+    let mut state = State::new(enum_map! {
+        Event::PaddingSent => vec![Trans(curr_index, 1.0)],
+        Event::LimitReached => vec![Trans(next_index, 1.0)],
+        _ => vec![],
+    });
+
+    let timeout = fixed_dist(interval);
+    let limit = fixed_dist(grid_l as f64);
+
+    state.action = Some(Action::SendPadding {
+        bypass: false,
+        replace: false,
+        timeout,
+        limit: Some(limit),
+    });
+
+    state
+}
+
+/// Generate a RegulaTor-style machine whose send rate decays geometrically
+/// from an initial surge rate, `rate(t) = initial_rate * decay.powf(t)`,
+/// approximated by `num_states` fixed-rate PADDING states of one second each.
+pub fn generate_regulator_machine(
+    initial_rate: f64,
+    decay: f64,
+    num_states: usize,
+    allowed_padding_packets: u64,
+    max_padding_frac: f64,
+) -> String {
+    assert!(num_states > 0, "num_states must be at least 1");
+
+    let mut states: Vec<State> = Vec::with_capacity(num_states + 1);
+    states.push(generate_start_state());
+
+    for i in 0..num_states {
+        let rate = initial_rate * decay.powf(i as f64);
+        let next_index = if i + 1 == num_states { STATE_END } else { i + 2 };
+        states.push(generate_rate_state(i + 1, next_index, rate.max(1.0)));
+    }
+
+    let machine = Machine {
+        allowed_padding_packets,
+        max_padding_frac,
+        allowed_blocked_microsec: 0,
+        max_blocking_frac: 0.0,
+        states,
+    };
+
+    machine.serialize()
+}
+
+// A PADDING state that sends for one second at a fixed `rate` (packets/sec)
+// before handing off to `next_index`.
+fn generate_rate_state(curr_index: usize, next_index: usize, rate: f64) -> State {
+    let mut state = State::new(enum_map! {
+        Event::PaddingSent => vec![Trans(curr_index, 1.0)],
+        Event::LimitReached => vec![Trans(next_index, 1.0)],
+        _ => vec![],
+    });
+
+    let timeout = fixed_dist(1.0 / rate);
+    let limit = fixed_dist(rate);
+
+    state.action = Some(Action::SendPadding {
+        bypass: false,
+        replace: false,
+        timeout,
+        limit: Some(limit),
+    });
+
+    state
+}
+
+/// Generate a WTF-PAD-style adaptive machine: on a real packet, picks a
+/// padding gap from a histogram of `(gap_seconds, probability)` buckets
+/// rather than a single timeout/jitter pair, sends padding after that gap,
+/// then returns to the histogram to pick the next one.
+pub fn generate_wtf_pad_machine(
+    histogram: &[(f64, f64)],
+    allowed_padding_packets: u64,
+    max_padding_frac: f64,
+) -> String {
+    assert!(!histogram.is_empty(), "histogram must have at least one bucket");
+
+    let total: f32 = histogram.iter().map(|(_, p)| *p as f32).sum();
+
+    let mut states: Vec<State> = Vec::with_capacity(histogram.len() + 1);
+    states.push(generate_histogram_start_state(histogram, total));
+
+    for (gap, _) in histogram {
+        states.push(generate_histogram_padding_state(*gap));
+    }
+
+    let machine = Machine {
+        allowed_padding_packets,
+        max_padding_frac,
+        allowed_blocked_microsec: 0,
+        max_blocking_frac: 0.0,
+        states,
+    };
+
+    machine.serialize()
+}
+
+// The START state: on a real packet, jumps into one of the histogram's
+// padding states, weighted by that bucket's observed probability.
+fn generate_histogram_start_state(histogram: &[(f64, f64)], total: f32) -> State {
+    let trans: Vec<Trans> = histogram
+        .iter()
+        .enumerate()
+        .map(|(i, (_, p))| Trans(i + 1, *p as f32 / total))
+        .collect();
+
+    State::new(enum_map! {
+        Event::NormalSent => trans.clone(),
+        Event::NormalRecv => trans,
+        _ => vec![],
+    })
+}
+
+// A PADDING state for a single histogram bucket: pads after the bucket's gap
+// (with a little jitter), then returns to the histogram to pick the next gap.
+fn generate_histogram_padding_state(gap: f64) -> State {
+    let mut state = State::new(enum_map! {
+        Event::PaddingSent => vec![Trans(0, 1.0)],
+        Event::LimitReached => vec![Trans(STATE_END, 1.0)],
+        _ => vec![],
+    });
+
+    let timeout = Dist::new(
+        DistType::Normal {
+            mean: gap,
+            stdev: gap / 10.0,
+        },
+        0.0,
+        gap * 2.0,
+    );
+
+    state.action = Some(Action::SendPadding {
+        bypass: false,
+        replace: false,
+        timeout,
+        limit: None,
+    });
+
+    state
+}
+
+// Generate the START state shared by the constant-rate and RegulaTor
+// builders: a real packet in either direction kicks off padding.
+fn generate_start_state() -> State {
+    State::new(enum_map! {
+        Event::NormalSent => vec![Trans(1, 1.0)],
+        Event::NormalRecv => vec![Trans(1, 1.0)],
+        _ => vec![],
+    })
+}
@@ -0,0 +1,263 @@
+//! Website-fingerprinting attack-and-overhead evaluation harness.
+//!
+//! Consumes the packet trace produced by `maybenot_simulator::sim` over a
+//! labeled dataset and reports both overhead (bandwidth overhead = padding
+//! bytes / real bytes, and latency overhead from blocking) and attack
+//! accuracy against a built-in k-fingerprinting-style classifier: a fixed
+//! feature vector per trace, classified with k-nearest-neighbors, reporting
+//! top-1/top-k accuracy and a per-class confusion matrix.
+//!
+//! Feature extraction is adapted from the flow feature-extraction pipeline
+//! used by network detection engines (e.g. Suricata's app-layer/detection
+//! modules).
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// A single packet in a simulated trace, reduced to the fields the evaluator
+/// needs: its offset from the start of the trace, direction, whether it is
+/// padding, and its size in bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct EvalPacket {
+    pub time: Duration,
+    pub outgoing: bool,
+    pub is_padding: bool,
+    pub size: u64,
+}
+
+/// A defended trace together with its website label.
+pub struct LabeledTrace {
+    pub label: usize,
+    pub packets: Vec<EvalPacket>,
+}
+
+/// Bandwidth and latency overhead of a defended trace relative to the
+/// undefended trace it was built from.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Overhead {
+    pub bandwidth_overhead: f64,
+    pub latency_overhead: f64,
+}
+
+/// Compute overhead for one defended trace: bandwidth overhead is padding
+/// bytes over real bytes, latency overhead is the fractional increase in the
+/// time of the last real packet versus `undefended_last` (the same point in
+/// the undefended trace), capturing delay from blocking actions.
+pub fn compute_overhead(packets: &[EvalPacket], undefended_last: Duration) -> Overhead {
+    let real_bytes: u64 = packets.iter().filter(|p| !p.is_padding).map(|p| p.size).sum();
+    let padding_bytes: u64 = packets.iter().filter(|p| p.is_padding).map(|p| p.size).sum();
+
+    let bandwidth_overhead = if real_bytes == 0 {
+        0.0
+    } else {
+        padding_bytes as f64 / real_bytes as f64
+    };
+
+    let defended_last = packets
+        .iter()
+        .filter(|p| !p.is_padding)
+        .map(|p| p.time)
+        .max()
+        .unwrap_or(Duration::ZERO);
+
+    let latency_overhead = if undefended_last.is_zero() {
+        0.0
+    } else {
+        (defended_last.as_secs_f64() - undefended_last.as_secs_f64()) / undefended_last.as_secs_f64()
+    };
+
+    Overhead {
+        bandwidth_overhead,
+        latency_overhead,
+    }
+}
+
+const NUM_TIME_WINDOWS: usize = 10;
+const FEATURE_LEN: usize = 6 + NUM_TIME_WINDOWS;
+
+/// Extract a fixed-length k-fingerprinting-style feature vector: total
+/// packet count, outgoing/incoming counts and fractions, a cumulative-size
+/// burst total, and packet counts within `NUM_TIME_WINDOWS` fixed windows
+/// spanning the trace.
+pub fn extract_features(packets: &[EvalPacket]) -> [f64; FEATURE_LEN] {
+    let mut features = [0.0; FEATURE_LEN];
+    if packets.is_empty() {
+        return features;
+    }
+
+    let total = packets.len() as f64;
+    let outgoing = packets.iter().filter(|p| p.outgoing).count() as f64;
+    let incoming = total - outgoing;
+
+    features[0] = total;
+    features[1] = outgoing;
+    features[2] = incoming;
+    features[3] = outgoing / total;
+    features[4] = incoming / total;
+    features[5] = packets.iter().map(|p| p.size as f64).sum();
+
+    let last = packets.last().unwrap().time.as_secs_f64();
+    if last > 0.0 {
+        for p in packets {
+            let window = ((p.time.as_secs_f64() / last) * NUM_TIME_WINDOWS as f64) as usize;
+            features[6 + window.min(NUM_TIME_WINDOWS - 1)] += 1.0;
+        }
+    }
+
+    features
+}
+
+fn euclidean_distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// A k-nearest-neighbors classifier over extracted feature vectors, trained
+/// on a labeled set of defended traces.
+pub struct KnnClassifier {
+    k: usize,
+    train_features: Vec<[f64; FEATURE_LEN]>,
+    train_labels: Vec<usize>,
+}
+
+impl KnnClassifier {
+    pub fn train(traces: &[LabeledTrace], k: usize) -> Self {
+        KnnClassifier {
+            k,
+            train_features: traces.iter().map(|t| extract_features(&t.packets)).collect(),
+            train_labels: traces.iter().map(|t| t.label).collect(),
+        }
+    }
+
+    /// Rank candidate labels by vote count among the `k` nearest neighbors,
+    /// most likely first. The first entry is the top-1 prediction; the first
+    /// `k` entries are the top-k candidates.
+    pub fn predict_ranked(&self, features: &[f64]) -> Vec<usize> {
+        let mut distances: Vec<(f64, usize)> = self
+            .train_features
+            .iter()
+            .zip(&self.train_labels)
+            .map(|(f, &label)| (euclidean_distance(features, f), label))
+            .collect();
+        distances.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        // A BTreeMap (rather than a HashMap) keeps iteration in ascending
+        // label order, so the stable sort below breaks vote-count ties by
+        // label instead of by HashMap's unspecified, per-process order.
+        let mut votes: BTreeMap<usize, usize> = BTreeMap::new();
+        for (_, label) in distances.into_iter().take(self.k) {
+            *votes.entry(label).or_insert(0) += 1;
+        }
+
+        let mut ranked: Vec<(usize, usize)> = votes.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1));
+        ranked.into_iter().map(|(label, _)| label).collect()
+    }
+}
+
+/// Accuracy and confusion-matrix report from evaluating a trained classifier
+/// against a held-out test split.
+pub struct EvalReport {
+    pub top1_accuracy: f64,
+    pub topk_accuracy: f64,
+    pub confusion: Vec<Vec<usize>>,
+}
+
+/// Train a k-NN classifier on `train` and report top-1/top-k accuracy and a
+/// `num_classes`-by-`num_classes` confusion matrix over `test`.
+pub fn evaluate(
+    train: &[LabeledTrace],
+    test: &[LabeledTrace],
+    num_classes: usize,
+    k: usize,
+) -> EvalReport {
+    let classifier = KnnClassifier::train(train, k);
+
+    let mut confusion = vec![vec![0usize; num_classes]; num_classes];
+    let mut top1_correct = 0;
+    let mut topk_correct = 0;
+
+    for trace in test {
+        let features = extract_features(&trace.packets);
+        let ranked = classifier.predict_ranked(&features);
+
+        if let Some(&predicted) = ranked.first() {
+            confusion[trace.label][predicted] += 1;
+            if predicted == trace.label {
+                top1_correct += 1;
+            }
+        }
+        if ranked.iter().take(k).any(|&label| label == trace.label) {
+            topk_correct += 1;
+        }
+    }
+
+    let total = test.len().max(1) as f64;
+    EvalReport {
+        top1_accuracy: top1_correct as f64 / total,
+        topk_accuracy: topk_correct as f64 / total,
+        confusion,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(time_ms: u64, size: u64) -> EvalPacket {
+        EvalPacket {
+            time: Duration::from_millis(time_ms),
+            outgoing: true,
+            is_padding: false,
+            size,
+        }
+    }
+
+    #[test]
+    fn predict_ranked_picks_nearest_label() {
+        let traces = vec![
+            LabeledTrace {
+                label: 0,
+                packets: vec![packet(0, 100), packet(10, 100)],
+            },
+            LabeledTrace {
+                label: 1,
+                packets: vec![packet(0, 900), packet(10, 900), packet(20, 900)],
+            },
+        ];
+        let classifier = KnnClassifier::train(&traces, 1);
+
+        let query = extract_features(&[packet(0, 100), packet(10, 100)]);
+        assert_eq!(classifier.predict_ranked(&query).first(), Some(&0));
+    }
+
+    #[test]
+    fn predict_ranked_breaks_ties_by_label_deterministically() {
+        // Two training points with different labels, equidistant from the
+        // query: a k=2 vote is a 1-1 tie, which must resolve the same way on
+        // every run rather than depending on hash iteration order.
+        let traces = vec![
+            LabeledTrace {
+                label: 5,
+                packets: vec![packet(0, 100)],
+            },
+            LabeledTrace {
+                label: 2,
+                packets: vec![packet(0, 100)],
+            },
+        ];
+        let classifier = KnnClassifier::train(&traces, 2);
+        let query = extract_features(&[packet(0, 100)]);
+
+        let first = classifier.predict_ranked(&query);
+        for _ in 0..10 {
+            assert_eq!(classifier.predict_ranked(&query), first);
+        }
+        // BTreeMap iterates labels in ascending order, so the stable sort
+        // over equal vote counts keeps the lower label first.
+        assert_eq!(first.first(), Some(&2));
+    }
+}
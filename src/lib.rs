@@ -0,0 +1,10 @@
+//! Supporting library for the `maybenot-defenses` examples: a congestion-aware
+//! network link model, parametric defense builders, padding-size control, a
+//! dataset loader and a website-fingerprinting evaluation harness, used by the
+//! `maybenot_eval` binary.
+
+pub mod dataset;
+pub mod defenses;
+pub mod eval;
+pub mod link;
+pub mod sizing;
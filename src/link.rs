@@ -0,0 +1,195 @@
+//! Congestion-control-aware network link model.
+//!
+//! `maybenot_simulator::network::Network` (as used in `maybenot_simulator`
+//! and `maybenot_simulator_regulator`) only models a fixed one-way delay, so
+//! injected padding from a generator like `maybenot_front` competes with real
+//! traffic in an unrealistic way. A [`LinkModel`] adds an optional
+//! bottleneck on top of that delay: a bandwidth, a finite queue, a loss rate,
+//! and a pluggable [`congestion::CongestionController`] (NewReno or CUBIC).
+//! [`LinkModel::apply_to_trace`] replays a simulated trace's packets through
+//! [`LinkModel::admit`], so that padding genuinely consumes capacity and can
+//! displace or delay real packets, rather than an idealized constant-delay
+//! link.
+
+pub mod congestion;
+
+pub use congestion::CongestionController;
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::eval::EvalPacket;
+
+/// A bottleneck link: a bandwidth, a finite queue (in bytes), a loss rate,
+/// and the congestion controller governing how much of that bandwidth a
+/// sender may use at any one time.
+#[derive(Debug, Clone)]
+pub struct LinkModel {
+    pub bandwidth_bps: u64,
+    pub queue_capacity_bytes: u64,
+    pub loss_rate: f64,
+    pub rtt: Duration,
+    pub controller: CongestionController,
+    seed: u64,
+    queued_bytes: u64,
+    // Packets currently occupying the congestion window, as (departure time,
+    // bytes, whether those bytes were added to `queued_bytes`), oldest
+    // first, so `admit` can free up window/queue space as packets actually
+    // leave the link instead of only ever growing, and only give back
+    // `queued_bytes` that were actually taken from it.
+    in_flight: VecDeque<(Duration, u64, bool)>,
+    last_ack: Duration,
+}
+
+impl LinkModel {
+    pub fn new(
+        bandwidth_bps: u64,
+        queue_capacity_bytes: u64,
+        loss_rate: f64,
+        rtt: Duration,
+        controller: CongestionController,
+        seed: u64,
+    ) -> Self {
+        LinkModel {
+            bandwidth_bps,
+            queue_capacity_bytes,
+            loss_rate,
+            rtt,
+            controller,
+            seed,
+            queued_bytes: 0,
+            in_flight: VecDeque::new(),
+            last_ack: Duration::ZERO,
+        }
+    }
+
+    /// Reseed this link, e.g. after cloning a shared template for a new
+    /// trace, so its loss draws are reproducible per trace.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Present a packet of `size` bytes to the link at time `now`. Returns
+    /// the extra delay (beyond the network's base delay) before the packet
+    /// may be released, or `None` if it is dropped: the congestion window is
+    /// full and the queue overflows, or it is picked by `loss_rate`.
+    pub fn admit(&mut self, size: u64, now: Duration) -> Option<Duration> {
+        // Free up window/queue space for anything that has already departed
+        // by `now`, before deciding this packet's fate. Only packets that
+        // were actually queued (the `bool` flag) ever contributed to
+        // `queued_bytes`, so only those give it back.
+        while let Some(&(departs, bytes, queued)) = self.in_flight.front() {
+            if departs > now {
+                break;
+            }
+            self.in_flight.pop_front();
+            if queued {
+                self.queued_bytes = self.queued_bytes.saturating_sub(bytes);
+            }
+        }
+
+        if self.loss_rate > 0.0 && pseudo_random(self.seed, now, size) < self.loss_rate {
+            self.controller.on_loss();
+            return None;
+        }
+
+        let window_bytes = self.controller.cwnd();
+        let in_flight_bytes: f64 = self.in_flight.iter().map(|&(_, b, _)| b as f64).sum();
+        let queued = in_flight_bytes + size as f64 > window_bytes;
+        if queued {
+            if self.queued_bytes + size > self.queue_capacity_bytes {
+                return None;
+            }
+            self.queued_bytes += size;
+        }
+
+        // Grow the window once per RTT of elapsed time, not once per packet.
+        if now.saturating_sub(self.last_ack) >= self.rtt {
+            self.controller.on_rtt_ack(self.rtt.as_secs_f64());
+            self.last_ack = now;
+        }
+
+        let queue_delay_secs = self.queued_bytes as f64 / self.bandwidth_bps as f64;
+        let delay = Duration::from_secs_f64(queue_delay_secs);
+        self.in_flight.push_back((now + delay + self.rtt, size, queued));
+        Some(delay)
+    }
+
+    /// Replay an already-simulated trace's packets through this link, in
+    /// order, delaying each by what `admit` returns and dropping whatever
+    /// `admit` rejects, so a trace from `maybenot_simulator::sim` reflects
+    /// real transport back-pressure instead of an idealized constant delay.
+    pub fn apply_to_trace(&mut self, packets: Vec<EvalPacket>) -> Vec<EvalPacket> {
+        packets
+            .into_iter()
+            .filter_map(|mut packet| {
+                let delay = self.admit(packet.size, packet.time)?;
+                packet.time += delay;
+                Some(packet)
+            })
+            .collect()
+    }
+}
+
+// Deterministic stand-in for a per-packet loss coin-flip: hashes the link's
+// seed together with the scheduling time and packet size, so repeated runs
+// of the same trace are reproducible without threading an RNG through the
+// simulator loop.
+fn pseudo_random(seed: u64, now: Duration, size: u64) -> f64 {
+    let mut x = seed ^ now.as_nanos() as u64 ^ size.wrapping_mul(0x9E3779B97F4A7C15);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51afd7ed558ccd);
+    x ^= x >> 33;
+    (x % 1_000_000) as f64 / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_drains_once_queued_packets_depart() {
+        // A tiny window forces every packet into the queue; bandwidth is
+        // generous so the only thing worth asserting is that, once the earlier
+        // packets have departed, `queued_bytes` holds only what the newest
+        // packet itself queued, not leftover bytes from the departed ones.
+        let mut link = LinkModel::new(
+            1_000_000_000,
+            10_000,
+            0.0,
+            Duration::from_millis(100),
+            CongestionController::new_reno(1000.0, 1.0),
+            42,
+        );
+
+        let delay_a = link.admit(500, Duration::ZERO).expect("admitted");
+        let delay_b = link.admit(500, Duration::from_millis(1)).expect("admitted");
+        assert!(link.queued_bytes > 0);
+
+        // Advance past both packets' departure times.
+        let departed = Duration::from_millis(1) + delay_a.max(delay_b) + link.rtt + Duration::from_millis(1);
+        let delay_c = link.admit(500, departed).expect("admitted");
+        // With both earlier packets drained, this one should only reflect
+        // its own queueing, not leftover bytes from packets that already left.
+        assert!(delay_c <= delay_a.max(delay_b));
+        assert_eq!(
+            link.queued_bytes,
+            if link.controller.cwnd() < 500.0 { 500 } else { 0 }
+        );
+    }
+
+    #[test]
+    fn loss_drops_the_packet() {
+        let mut link = LinkModel::new(
+            1_000_000_000,
+            10_000,
+            1.0,
+            Duration::from_millis(50),
+            CongestionController::cubic(10_000.0),
+            7,
+        );
+
+        assert_eq!(link.admit(500, Duration::ZERO), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! Congestion controllers for [`super::LinkModel`].
+//!
+//! Both controllers track a congestion window `cwnd` in bytes and react to
+//! per-RTT feedback the way the classic_cc / new_reno / cubic modules in the
+//! neqo transport stack do: NewReno's slow-start/congestion-avoidance split,
+//! and CUBIC's cubic growth function around a remembered `w_max`.
+
+const CUBIC_C: f64 = 0.4;
+const CUBIC_BETA: f64 = 0.7;
+
+/// NewReno: doubles `cwnd` per RTT in slow start until `ssthresh`, then grows
+/// it by one `mss` per RTT in congestion avoidance. On loss, halves `cwnd`
+/// into `ssthresh`.
+#[derive(Debug, Clone)]
+pub struct NewReno {
+    mss: f64,
+    cwnd: f64,
+    ssthresh: f64,
+}
+
+impl NewReno {
+    pub fn new(mss: f64, init_cwnd: f64) -> Self {
+        NewReno {
+            mss,
+            cwnd: init_cwnd,
+            ssthresh: f64::MAX,
+        }
+    }
+
+    fn on_rtt_ack(&mut self) {
+        if self.cwnd < self.ssthresh {
+            self.cwnd += self.cwnd;
+        } else {
+            self.cwnd += self.mss;
+        }
+    }
+
+    fn on_loss(&mut self) {
+        self.ssthresh = self.cwnd / 2.0;
+        self.cwnd = self.ssthresh;
+    }
+
+    fn cwnd(&self) -> f64 {
+        self.cwnd
+    }
+}
+
+/// CUBIC: `W(t) = C*(t - K)^3 + W_max`, with `K = cbrt(W_max*(1-beta)/C)`
+/// measured from the time of the last window reduction. On loss, remembers
+/// `W_max = cwnd` and cuts `cwnd` to `cwnd * beta`.
+#[derive(Debug, Clone)]
+pub struct Cubic {
+    cwnd: f64,
+    w_max: f64,
+    k: f64,
+    t: f64,
+}
+
+impl Cubic {
+    pub fn new(init_cwnd: f64) -> Self {
+        Cubic {
+            cwnd: init_cwnd,
+            w_max: init_cwnd,
+            k: 0.0,
+            t: 0.0,
+        }
+    }
+
+    fn on_rtt_ack(&mut self, rtt_secs: f64) {
+        self.t += rtt_secs;
+        self.cwnd = CUBIC_C * (self.t - self.k).powi(3) + self.w_max;
+    }
+
+    fn on_loss(&mut self) {
+        self.w_max = self.cwnd;
+        self.k = (self.w_max * (1.0 - CUBIC_BETA) / CUBIC_C).cbrt();
+        self.cwnd *= CUBIC_BETA;
+        self.t = 0.0;
+    }
+
+    fn cwnd(&self) -> f64 {
+        self.cwnd
+    }
+}
+
+/// A selectable congestion controller, picked per simulation run.
+#[derive(Debug, Clone)]
+pub enum CongestionController {
+    NewReno(NewReno),
+    Cubic(Cubic),
+}
+
+impl CongestionController {
+    pub fn new_reno(mss: f64, init_cwnd: f64) -> Self {
+        CongestionController::NewReno(NewReno::new(mss, init_cwnd))
+    }
+
+    pub fn cubic(init_cwnd: f64) -> Self {
+        CongestionController::Cubic(Cubic::new(init_cwnd))
+    }
+
+    /// Current congestion window, in bytes.
+    pub fn cwnd(&self) -> f64 {
+        match self {
+            CongestionController::NewReno(c) => c.cwnd(),
+            CongestionController::Cubic(c) => c.cwnd(),
+        }
+    }
+
+    /// Grow the window by one RTT's worth of acknowledged data.
+    pub fn on_rtt_ack(&mut self, rtt_secs: f64) {
+        match self {
+            CongestionController::NewReno(c) => c.on_rtt_ack(),
+            CongestionController::Cubic(c) => c.on_rtt_ack(rtt_secs),
+        }
+    }
+
+    /// React to a detected loss.
+    pub fn on_loss(&mut self) {
+        match self {
+            CongestionController::NewReno(c) => c.on_loss(),
+            CongestionController::Cubic(c) => c.on_loss(),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Padding-packet size control.
+//!
+//! `Action::SendPadding` has no size field in this version of `maybenot`, so
+//! [`apply_padding_sizes`] sizes padding packets in a defended trace after
+//! the fact, from a [`SizeDist`] (uniform or empirical); see
+//! `dataset::simulate_file` for the call site.
+
+use crate::eval::EvalPacket;
+
+/// A distribution over synthetic packet sizes, in bytes.
+#[derive(Debug, Clone)]
+pub enum SizeDist {
+    Uniform { low: u64, high: u64 },
+    /// Packet sizes observed in a real trace, sampled uniformly at random
+    /// (with replacement).
+    Empirical(Vec<u64>),
+}
+
+impl SizeDist {
+    /// Build an empirical size distribution from a real trace's observed
+    /// packet sizes.
+    pub fn empirical_from_trace(sizes: &[u64]) -> Self {
+        SizeDist::Empirical(sizes.to_vec())
+    }
+
+    /// Deterministically sample a size for the `n`th padding packet under
+    /// `seed`, so repeated runs over the same trace produce the same sizes.
+    pub fn sample(&self, seed: u64, n: u64) -> u64 {
+        match self {
+            SizeDist::Uniform { low, high } => {
+                if *high <= *low {
+                    *low
+                } else {
+                    low + pseudo_random(seed, n) % (high - low + 1)
+                }
+            }
+            SizeDist::Empirical(sizes) => {
+                if sizes.is_empty() {
+                    0
+                } else {
+                    sizes[(pseudo_random(seed, n) as usize) % sizes.len()]
+                }
+            }
+        }
+    }
+}
+
+// Deterministic stand-in for an RNG draw, keyed by the trace's seed and the
+// padding packet's sequence number so the same trace always samples the
+// same sizes.
+fn pseudo_random(seed: u64, n: u64) -> u64 {
+    let mut x = seed ^ n.wrapping_mul(0x9E3779B97F4A7C15);
+    x ^= x >> 33;
+    x = x.wrapping_mul(0xff51afd7ed558ccd);
+    x ^= x >> 33;
+    x
+}
+
+/// Size every padding packet in `packets` by sampling from `dist` under
+/// `seed`, in the order padding packets appear in the trace, leaving real
+/// packets' `size` untouched.
+pub fn apply_padding_sizes(packets: &mut [EvalPacket], dist: &SizeDist, seed: u64) {
+    let mut n = 0u64;
+    for packet in packets.iter_mut() {
+        if packet.is_padding {
+            packet.size = dist.sample(seed, n);
+            n += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uniform_sample_stays_in_range() {
+        let dist = SizeDist::Uniform { low: 100, high: 150 };
+        for n in 0..100 {
+            let size = dist.sample(42, n);
+            assert!((100..=150).contains(&size));
+        }
+    }
+
+    #[test]
+    fn uniform_sample_is_deterministic_per_seed_and_index() {
+        let dist = SizeDist::Uniform { low: 0, high: 1500 };
+        assert_eq!(dist.sample(7, 3), dist.sample(7, 3));
+    }
+
+    #[test]
+    fn empirical_sample_only_returns_observed_sizes() {
+        let observed = vec![512, 1024, 1500];
+        let dist = SizeDist::empirical_from_trace(&observed);
+        for n in 0..50 {
+            assert!(observed.contains(&dist.sample(1, n)));
+        }
+    }
+
+    #[test]
+    fn empirical_sample_of_empty_distribution_is_zero() {
+        let dist = SizeDist::empirical_from_trace(&[]);
+        assert_eq!(dist.sample(1, 0), 0);
+    }
+}